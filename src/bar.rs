@@ -0,0 +1,10 @@
+//! The status bar shown at the bottom of the screen.
+
+use termwiz::cell::CellAttributes;
+
+use crate::config::{ColorItem, Config};
+
+/// The attributes the status bar should be drawn with.
+pub(crate) fn attributes(config: &Config) -> CellAttributes {
+    config.colors.get(ColorItem::Status)
+}
@@ -0,0 +1,368 @@
+//! Pager configuration.
+
+use std::time::Duration;
+
+use anyhow::{anyhow, bail, Result};
+use termwiz::cell::{CellAttributes, Intensity, Underline};
+use termwiz::color::{AnsiColor, ColorAttribute, RgbColor};
+use vec_map::VecMap;
+
+/// Controls when the pager switches into full screen interactive mode.
+#[derive(Clone, Debug)]
+pub enum InterfaceMode {
+    /// Always use the full screen interface.
+    Full,
+
+    /// Use the full screen interface. The full screen interface is
+    /// entered straight away, exactly as for `Full`; the duration is
+    /// accepted but not used.
+    Delayed(Duration),
+
+    /// Never use the full screen interface; write content directly to the
+    /// terminal as it arrives.
+    Direct,
+
+    /// Measure the content once loading finishes (or once
+    /// `read_ahead_lines` worth of content is buffered for files that are
+    /// still streaming): if it fits within a single screen, print it
+    /// directly to the terminal instead of entering the full screen
+    /// interface; otherwise fall back to the normal full screen pager
+    /// immediately.
+    ///
+    /// This mirrors `bat`'s `PagingMode::QuitIfOneScreen` and lets callers
+    /// use the pager unconditionally without stranding short output behind
+    /// an interactive UI.
+    QuitIfOneScreen,
+}
+
+impl Default for InterfaceMode {
+    fn default() -> Self {
+        InterfaceMode::Delayed(Duration::from_millis(200))
+    }
+}
+
+impl From<bool> for InterfaceMode {
+    fn from(full_screen: bool) -> Self {
+        if full_screen {
+            InterfaceMode::Full
+        } else {
+            InterfaceMode::Direct
+        }
+    }
+}
+
+/// A themeable piece of the pager's UI.
+///
+/// Used as the `{item}` in a `{item}:{attr}:{value}` color spec (see
+/// [`Colors::apply_spec`]).
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum ColorItem {
+    /// A search match other than the first one on its line.
+    Match,
+
+    /// The first search match on each rendered line. No single match is
+    /// tracked as selected across lines, so every line with a match has
+    /// one drawn this way.
+    MatchCurrent,
+
+    /// The status bar at the bottom of the screen.
+    Status,
+
+    /// The ruler showing the current position in the file.
+    Ruler,
+
+    /// Progress indicator text.
+    Progress,
+}
+
+impl ColorItem {
+    fn all() -> &'static [ColorItem] {
+        &[
+            ColorItem::Match,
+            ColorItem::MatchCurrent,
+            ColorItem::Status,
+            ColorItem::Ruler,
+            ColorItem::Progress,
+        ]
+    }
+
+    fn index(self) -> usize {
+        self as usize
+    }
+
+    fn parse(name: &str) -> Result<Self> {
+        Ok(match name {
+            "match" => ColorItem::Match,
+            "match-current" => ColorItem::MatchCurrent,
+            "status" | "bar" => ColorItem::Status,
+            "ruler" => ColorItem::Ruler,
+            "progress" => ColorItem::Progress,
+            _ => bail!("unknown color item {:?} (expected one of: match, match-current, status, ruler, progress)", name),
+        })
+    }
+
+    /// The item's hardcoded default appearance.
+    fn default_attributes(self) -> CellAttributes {
+        let mut attrs = CellAttributes::default();
+        match self {
+            ColorItem::Match => {
+                attrs.set_background(ColorAttribute::PaletteIndex(AnsiColor::Yellow as u8));
+                attrs.set_foreground(ColorAttribute::PaletteIndex(AnsiColor::Black as u8));
+            }
+            ColorItem::MatchCurrent => {
+                attrs.set_background(ColorAttribute::PaletteIndex(AnsiColor::Green as u8));
+                attrs.set_foreground(ColorAttribute::PaletteIndex(AnsiColor::Black as u8));
+            }
+            ColorItem::Status | ColorItem::Ruler => {
+                attrs.set_reverse(true);
+            }
+            ColorItem::Progress => {
+                attrs.set_foreground(ColorAttribute::PaletteIndex(AnsiColor::Cyan as u8));
+            }
+        }
+        attrs
+    }
+}
+
+/// The `{attr}` in a `{item}:{attr}:{value}` color spec: which aspect of
+/// the item's appearance is being set.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+enum ColorAttr {
+    Fg,
+    Bg,
+    Style,
+}
+
+impl ColorAttr {
+    fn parse(name: &str) -> Result<Self> {
+        Ok(match name {
+            "fg" => ColorAttr::Fg,
+            "bg" => ColorAttr::Bg,
+            "style" => ColorAttr::Style,
+            _ => bail!("unknown color attribute {:?} (expected one of: fg, bg, style)", name),
+        })
+    }
+}
+
+/// The resolved colors and styles for every themeable item in the UI.
+///
+/// Starts out with each item's default appearance (see
+/// `ColorItem::default_attributes`); individual
+/// items are overridden by applying specs of the form
+/// `{item}:{attr}:{value}` (see [`Colors::apply_spec`]), e.g.
+/// `match:fg:magenta` or `ruler:bg:#202020`.
+#[derive(Clone, Debug)]
+pub(crate) struct Colors(VecMap<CellAttributes>);
+
+impl Default for Colors {
+    fn default() -> Self {
+        let mut map = VecMap::new();
+        for item in ColorItem::all() {
+            map.insert(item.index(), item.default_attributes());
+        }
+        Colors(map)
+    }
+}
+
+impl Colors {
+    /// The resolved attributes for `item`.
+    pub(crate) fn get(&self, item: ColorItem) -> CellAttributes {
+        self.0
+            .get(item.index())
+            .cloned()
+            .unwrap_or_else(|| item.default_attributes())
+    }
+
+    /// Parse a `{item}:{attr}:{value}` spec and apply it, overriding that
+    /// item's previous appearance for the given attribute.
+    ///
+    /// Returns an error describing the problem if the spec is malformed,
+    /// rather than silently ignoring it.
+    pub(crate) fn apply_spec(&mut self, spec: &str) -> Result<()> {
+        let mut parts = spec.splitn(3, ':');
+        let item = parts
+            .next()
+            .ok_or_else(|| anyhow!("empty color spec"))?;
+        let attr = parts
+            .next()
+            .ok_or_else(|| anyhow!("color spec {:?} is missing an attribute (expected item:attr:value)", spec))?;
+        let value = parts
+            .next()
+            .ok_or_else(|| anyhow!("color spec {:?} is missing a value (expected item:attr:value)", spec))?;
+
+        let item = ColorItem::parse(item)?;
+        let attr = ColorAttr::parse(attr)?;
+        let mut attrs = self.get(item);
+        match attr {
+            ColorAttr::Fg => {
+                attrs.set_foreground(parse_color(value)?);
+            }
+            ColorAttr::Bg => {
+                attrs.set_background(parse_color(value)?);
+            }
+            ColorAttr::Style => apply_style(&mut attrs, value)?,
+        }
+        self.0.insert(item.index(), attrs);
+        Ok(())
+    }
+}
+
+/// Parse a color value: a named ANSI color, a `0..=255` palette index, or
+/// an `r,g,b` / `#rrggbb` truecolor triple.
+fn parse_color(value: &str) -> Result<ColorAttribute> {
+    if let Some(hex) = value.strip_prefix('#') {
+        let rgb = RgbColor::from_hex_str(&format!("#{}", hex))
+            .map_err(|_| anyhow!("invalid truecolor value {:?} (expected #rrggbb)", value))?;
+        return Ok(ColorAttribute::TrueColorWithDefaultFallback(rgb.into()));
+    }
+    if value.contains(',') {
+        let parts: Vec<&str> = value.split(',').collect();
+        if parts.len() != 3 {
+            bail!("invalid truecolor value {:?} (expected r,g,b)", value);
+        }
+        let mut channels = [0u8; 3];
+        for (channel, part) in channels.iter_mut().zip(parts) {
+            *channel = part
+                .trim()
+                .parse()
+                .map_err(|_| anyhow!("invalid color channel {:?} in {:?}", part, value))?;
+        }
+        let [r, g, b] = channels;
+        return Ok(ColorAttribute::TrueColorWithDefaultFallback(
+            RgbColor::new(r, g, b).into(),
+        ));
+    }
+    if let Ok(index) = value.parse::<u16>() {
+        if index > 255 {
+            bail!("palette index {} is out of range (expected 0..=255)", index);
+        }
+        return Ok(ColorAttribute::PaletteIndex(index as u8));
+    }
+    let ansi = match value {
+        "black" => AnsiColor::Black,
+        "red" => AnsiColor::Maroon,
+        "green" => AnsiColor::Green,
+        "yellow" => AnsiColor::Olive,
+        "blue" => AnsiColor::Navy,
+        "magenta" => AnsiColor::Purple,
+        "cyan" => AnsiColor::Teal,
+        "white" => AnsiColor::Silver,
+        "brightblack" => AnsiColor::Grey,
+        "brightred" => AnsiColor::Red,
+        "brightgreen" => AnsiColor::Lime,
+        "brightyellow" => AnsiColor::Yellow,
+        "brightblue" => AnsiColor::Blue,
+        "brightmagenta" => AnsiColor::Fuchsia,
+        "brightcyan" => AnsiColor::Aqua,
+        "brightwhite" => AnsiColor::White,
+        _ => bail!(
+            "unknown color {:?} (expected a named ANSI color, a 0..=255 palette index, or an r,g,b/#rrggbb triple)",
+            value
+        ),
+    };
+    Ok(ColorAttribute::PaletteIndex(ansi as u8))
+}
+
+/// Apply a named style (`bold`, `underline`, `reverse`, ...) to `attrs`.
+fn apply_style(attrs: &mut CellAttributes, value: &str) -> Result<()> {
+    for style in value.split('+') {
+        match style {
+            "bold" => {
+                attrs.set_intensity(Intensity::Bold);
+            }
+            "dim" => {
+                attrs.set_intensity(Intensity::Half);
+            }
+            "underline" => {
+                attrs.set_underline(Underline::Single);
+            }
+            "reverse" => {
+                attrs.set_reverse(true);
+            }
+            "italic" => {
+                attrs.set_italic(true);
+            }
+            "none" => {
+                *attrs = CellAttributes::default();
+            }
+            _ => bail!(
+                "unknown style {:?} (expected one of: bold, dim, underline, reverse, italic, none)",
+                style
+            ),
+        }
+    }
+    Ok(())
+}
+
+/// Pager configuration.
+#[derive(Clone, Debug)]
+pub struct Config {
+    /// When to switch to the full screen interface.
+    pub(crate) interface_mode: InterfaceMode,
+
+    /// Whether scrolling can go past the end of the file.
+    pub(crate) scroll_past_eof: bool,
+
+    /// How many lines to read ahead before giving up on waiting for more
+    /// content (e.g. to decide whether content fits on one screen).
+    pub(crate) read_ahead_lines: usize,
+
+    /// Resolved colors and styles for themeable UI items.
+    pub(crate) colors: Colors,
+
+    /// The syntax highlighting theme to use.
+    pub(crate) theme: String,
+
+    /// An explicit syntax name overriding auto-detection, if set.
+    pub(crate) syntax: Option<String>,
+
+    /// How many columns a hard tab expands to. `0` passes tabs through
+    /// unchanged.
+    pub(crate) tab_width: usize,
+
+    /// The template used to render each progress bar. See
+    /// [`crate::Pager::set_progress_format`] for the supported
+    /// placeholders.
+    pub(crate) progress_format: String,
+
+    /// How long a progress bar can go without an update before it's
+    /// pruned from the display.
+    pub(crate) progress_prune_after: Duration,
+
+    /// How long to wait for `read_ahead_lines` worth of content (or EOF)
+    /// before giving up on measuring whether content fits one screen.
+    pub(crate) read_ahead_timeout: Duration,
+
+    /// The text currently being searched for, if any. Matches are
+    /// rendered using the `match`/`match-current` colors.
+    pub(crate) search_query: Option<String>,
+}
+
+impl Config {
+    /// Build the default configuration, then apply any overrides found in
+    /// the environment.
+    pub(crate) fn from_env() -> Self {
+        let mut colors = Colors::default();
+        if let Ok(spec) = std::env::var("STREAMPAGER_COLORS") {
+            // Best-effort: an invalid `STREAMPAGER_COLORS` shouldn't stop
+            // the pager from starting. Callers who want a hard error on a
+            // bad spec should validate it themselves via `Pager::set_color`.
+            for item in spec.split(',').filter(|item| !item.is_empty()) {
+                let _ = colors.apply_spec(item);
+            }
+        }
+        Config {
+            interface_mode: InterfaceMode::default(),
+            scroll_past_eof: false,
+            read_ahead_lines: 1000,
+            colors,
+            theme: crate::highlight::DEFAULT_THEME.to_string(),
+            syntax: None,
+            tab_width: 8,
+            progress_format: "{msg} [{bar}] {pos}/{len} ({percent}) {elapsed}".to_string(),
+            progress_prune_after: Duration::from_secs(10),
+            read_ahead_timeout: Duration::from_millis(200),
+            search_query: None,
+        }
+    }
+}
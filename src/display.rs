@@ -0,0 +1,203 @@
+//! The main pager loop: decides between full screen interaction and
+//! direct passthrough, and drives whichever is chosen.
+
+use std::time::{Duration, Instant};
+
+use anyhow::Result;
+use termwiz::caps::Capabilities;
+use termwiz::terminal::{SystemTerminal, Terminal};
+use vec_map::VecMap;
+
+use crate::config::{Config, InterfaceMode};
+use crate::event::{Event, EventStream};
+use crate::file::File;
+use crate::line_drawing;
+use crate::progress::Progress;
+use crate::screen;
+use crate::signal::TerminalGuard;
+
+/// Drive the pager to completion: either by running the full screen
+/// interface, or, for [`InterfaceMode::QuitIfOneScreen`], by printing
+/// directly to the terminal and returning immediately when the content
+/// fits on one screen.
+pub(crate) fn start(
+    term: SystemTerminal,
+    caps: Capabilities,
+    events: EventStream,
+    files: Vec<File>,
+    error_files: VecMap<File>,
+    progress: Option<Progress>,
+    config: Config,
+) -> Result<()> {
+    match config.interface_mode {
+        InterfaceMode::QuitIfOneScreen => {
+            let (rows, cols) = terminal_size(&term)?;
+            if let Some(()) = fits_one_screen(&events, &files, &config, rows, cols)? {
+                print_loaded(&files)?;
+                return Ok(());
+            }
+        }
+        InterfaceMode::Direct => {
+            // Used both when callers ask for it directly, and as the
+            // automatic fallback when the UI's chosen output turns out
+            // not to be a terminal (see `Pager::new_using_stdio_and_stderr`).
+            print_direct(&events, &files)?;
+            return Ok(());
+        }
+        InterfaceMode::Full | InterfaceMode::Delayed(_) => {}
+    }
+
+    run_full_screen(term, caps, events, files, error_files, progress, config)
+}
+
+/// Measure the combined, wrapped line count of all attached files as they
+/// load, returning `Some(())` once it is established that everything fits
+/// within `rows` terminal rows, or `None` once it is clear that it does
+/// not, or once `config.read_ahead_timeout` has passed since the call
+/// began without every file reaching `read_ahead_lines` or EOF (so a
+/// still-streaming file that never quite reaches the read-ahead budget
+/// doesn't block the pager from starting forever).
+fn fits_one_screen(
+    events: &EventStream,
+    files: &[File],
+    config: &Config,
+    rows: usize,
+    cols: usize,
+) -> Result<Option<()>> {
+    let deadline = Instant::now() + config.read_ahead_timeout;
+    loop {
+        let mut total_rows = 0;
+        let mut all_ready = true;
+        for file in files {
+            // `lines_snapshot` already returns tab-expanded text (expansion
+            // happens once, in `Line::new`, as each line is read), so no
+            // further expansion is needed here.
+            let lines = file.lines_snapshot();
+            for line in &lines {
+                total_rows += line_drawing::wrapped_row_count(line, cols);
+            }
+            if total_rows > rows {
+                return Ok(None);
+            }
+            if !file.is_loaded() && lines.len() < config.read_ahead_lines {
+                all_ready = false;
+            }
+        }
+        if all_ready {
+            return Ok(Some(()));
+        }
+        let remaining = deadline.saturating_duration_since(Instant::now());
+        if remaining.is_zero() {
+            // The read-ahead budget expired without every file reaching
+            // EOF or `read_ahead_lines`: fall back to the full screen
+            // pager rather than waiting on a stalled stream forever.
+            return Ok(None);
+        }
+        events.recv_timeout(remaining.min(Duration::from_millis(50)));
+    }
+}
+
+/// Print every line currently loaded across every attached file directly
+/// to the terminal, merging them by the order lines actually arrived in
+/// (rather than file-by-file), so a subprocess's interleaved stdout and
+/// stderr stay interleaved instead of being reordered onto separate
+/// blocks. Used by [`InterfaceMode::QuitIfOneScreen`], which has already
+/// established that everything loaded so far fits on one screen and
+/// wants to print it and return immediately, without waiting for EOF.
+///
+/// `files` already includes every error stream attached via
+/// `add_error_stream`/`add_subprocess` — `error_files` only maps each
+/// output file's index to its error file for the display loop's benefit,
+/// so merging `files` alone covers everything without double-printing.
+fn print_loaded(files: &[File]) -> Result<()> {
+    use std::io::Write;
+
+    let mut lines: Vec<(u64, String)> = files.iter().flat_map(File::lines_with_sequence).collect();
+    lines.sort_by_key(|(seq, _)| *seq);
+
+    let stdout = std::io::stdout();
+    let mut out = stdout.lock();
+    for (_, text) in lines {
+        writeln!(out, "{}", text)?;
+    }
+    Ok(())
+}
+
+/// Stream every attached file's content directly to the terminal as it
+/// arrives, merged by true arrival order across files (see
+/// [`print_loaded`]), flushing after each batch so a downstream consumer
+/// in a pipeline sees output as it comes rather than only once every
+/// file reaches EOF. Returns once every file has finished loading.
+fn print_direct(events: &EventStream, files: &[File]) -> Result<()> {
+    use std::io::Write;
+
+    let mut printed = vec![0usize; files.len()];
+    let stdout = std::io::stdout();
+    let mut out = stdout.lock();
+
+    loop {
+        // Checked before this round's snapshot, so that lines appended
+        // just before a file reached EOF are still printed.
+        let all_loaded = files.iter().all(File::is_loaded);
+        let mut lines: Vec<(u64, String)> = Vec::new();
+        for (file, printed) in files.iter().zip(printed.iter_mut()) {
+            let available = file.lines_with_sequence();
+            lines.extend_from_slice(&available[*printed..]);
+            *printed = available.len();
+        }
+        lines.sort_by_key(|(seq, _)| *seq);
+        for (_, text) in lines {
+            writeln!(out, "{}", text)?;
+        }
+        out.flush()?;
+
+        if all_loaded {
+            return Ok(());
+        }
+        events.recv_timeout(Duration::from_millis(50));
+    }
+}
+
+/// Run the interactive, full screen pager.
+fn run_full_screen(
+    mut term: SystemTerminal,
+    caps: Capabilities,
+    events: EventStream,
+    files: Vec<File>,
+    _error_files: VecMap<File>,
+    progress: Option<Progress>,
+    config: Config,
+) -> Result<()> {
+    // Entering raw mode happens here, inside the guard, so that a quit
+    // event, an error, or a panic during rendering all unwind back through
+    // `TerminalGuard::drop` and leave the terminal in a usable state.
+    let _guard = TerminalGuard::enter(&mut term)?;
+
+    // The top (first visible) content line; scrolling the view changes
+    // this. Prompt and scroll key handling live elsewhere and would
+    // update it before triggering a redraw.
+    let top_line = 0;
+
+    screen::render(&mut term, &caps, &files, progress.as_ref(), &config, top_line)?;
+
+    loop {
+        match events.recv_timeout(Duration::from_millis(250)) {
+            Some(Event::Quit) => break,
+            // Every other event (new content, resizes, ...) can change
+            // what's on screen, so redraw from scratch rather than
+            // patching deltas. Prompt/search input handling lives
+            // elsewhere and would update `top_line`/`config` before
+            // triggering its own redraw.
+            Some(_) => screen::render(&mut term, &caps, &files, progress.as_ref(), &config, top_line)?,
+            None => {}
+        }
+    }
+
+    Ok(())
+}
+
+/// The terminal's current size, as (rows, columns).
+fn terminal_size(term: &SystemTerminal) -> Result<(usize, usize)> {
+    let size = term.get_screen_size()?;
+    Ok((size.rows, size.cols))
+}
@@ -0,0 +1,94 @@
+//! Event plumbing between background loaders and the display loop.
+
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::mpsc::{channel, Receiver, RecvTimeoutError, Sender};
+use std::sync::Arc;
+use std::time::Duration;
+
+use termwiz::terminal::Waker;
+
+/// An event that the display loop should react to.
+#[derive(Clone, Debug)]
+pub(crate) enum Event {
+    /// A file has new content available at the given index.
+    Loaded(usize),
+
+    /// A file has reached end-of-file at the given index.
+    Eof(usize),
+
+    /// The progress stream has new content available.
+    Progress,
+
+    /// The terminal was resized.
+    Resize,
+
+    /// The pager should quit.
+    Quit,
+}
+
+/// A stream of [`Event`]s fed by background file/progress loaders and
+/// consumed by the display loop.
+pub(crate) struct EventStream {
+    sender: EventSender,
+    receiver: Receiver<Event>,
+    waker: Waker,
+}
+
+impl EventStream {
+    /// Create a new event stream, using `waker` to wake the terminal's
+    /// input loop whenever an event is pushed.
+    pub(crate) fn new(waker: Waker) -> Self {
+        let (sender, receiver) = channel();
+        EventStream {
+            sender: EventSender {
+                sender,
+                waker: waker.clone(),
+                sequence: Arc::new(AtomicU64::new(0)),
+            },
+            receiver,
+            waker,
+        }
+    }
+
+    /// Get a handle that can be used to push events onto this stream from
+    /// another thread.
+    pub(crate) fn sender(&self) -> EventSender {
+        self.sender.clone()
+    }
+
+    /// Wait for the next event, up to `timeout`. Returns `None` on
+    /// timeout.
+    pub(crate) fn recv_timeout(&self, timeout: Duration) -> Option<Event> {
+        match self.receiver.recv_timeout(timeout) {
+            Ok(event) => Some(event),
+            Err(RecvTimeoutError::Timeout) => None,
+            Err(RecvTimeoutError::Disconnected) => None,
+        }
+    }
+}
+
+/// A handle used to push [`Event`]s onto an [`EventStream`] from another
+/// thread, waking the terminal so the display loop notices promptly.
+#[derive(Clone)]
+pub(crate) struct EventSender {
+    sender: Sender<Event>,
+    waker: Waker,
+    sequence: Arc<AtomicU64>,
+}
+
+impl EventSender {
+    /// Push an event onto the stream, waking the terminal's input loop so
+    /// it is noticed promptly.
+    pub(crate) fn send(&self, event: Event) {
+        let _ = self.sender.send(event);
+        let _ = self.waker.wake();
+    }
+
+    /// The next value in a monotonically increasing sequence shared by
+    /// every file and stream created from the same `Pager`. Used to reconstruct the true arrival order of lines
+    /// across multiple files, e.g. a subprocess's interleaved stdout and
+    /// stderr.
+    pub(crate) fn next_seq(&self) -> u64 {
+        self.sequence.fetch_add(1, Ordering::Relaxed)
+    }
+}
@@ -0,0 +1,231 @@
+//! Loading file and stream content in the background.
+
+use std::ffi::OsStr;
+use std::fs;
+use std::io::{BufRead, BufReader, Read};
+use std::path::Path;
+use std::process::{Command, Stdio};
+use std::sync::{Arc, Mutex};
+use std::thread;
+
+use anyhow::Result;
+use termwiz::caps::ColorLevel;
+
+use crate::config::Config;
+use crate::event::{Event, EventSender};
+use crate::highlight::{FileHighlighter, HighlightedSpan};
+use crate::line::Line;
+
+/// A file or stream being paged, loaded incrementally on a background
+/// thread.
+#[derive(Clone)]
+pub(crate) struct File(Arc<FileData>);
+
+struct FileData {
+    index: usize,
+    title: String,
+    /// Loaded lines, each tagged with the pager-wide arrival sequence
+    /// number it was appended under (see `EventSender::next_seq`), so
+    /// that output from multiple files (e.g. a subprocess's interleaved
+    /// stdout/stderr) can be reconstructed in the order it actually
+    /// arrived.
+    lines: Mutex<Vec<(u64, Line)>>,
+    loaded: Mutex<bool>,
+    highlighter: Mutex<FileHighlighter>,
+    /// How many leading lines have already been highlighted; highlighting
+    /// is only ever done up to the last line a caller has asked about, so
+    /// a huge file isn't highlighted further than it has actually been
+    /// viewed.
+    highlight_cursor: Mutex<usize>,
+}
+
+impl File {
+    /// Load `stream` on a background thread, splitting it into lines as
+    /// they arrive.
+    pub(crate) fn new_streamed(
+        index: usize,
+        stream: impl Read + Send + 'static,
+        title: &str,
+        event_sender: EventSender,
+        config: &Config,
+    ) -> Result<Self> {
+        let highlighter = FileHighlighter::new(
+            None,
+            title,
+            config.syntax.as_deref(),
+            &config.theme,
+        );
+        let data = Arc::new(FileData {
+            index,
+            title: title.to_string(),
+            lines: Mutex::new(Vec::new()),
+            loaded: Mutex::new(false),
+            highlighter: Mutex::new(highlighter),
+            highlight_cursor: Mutex::new(0),
+        });
+        spawn_reader(data.clone(), stream, event_sender, config.tab_width);
+        Ok(File(data))
+    }
+
+    /// Load the file at `filename` on a background thread.
+    pub(crate) fn new_file(
+        index: usize,
+        filename: &OsStr,
+        event_sender: EventSender,
+        config: &Config,
+    ) -> Result<Self> {
+        let title = filename.to_string_lossy().into_owned();
+        let highlighter = FileHighlighter::new(
+            Some(Path::new(filename)),
+            &title,
+            config.syntax.as_deref(),
+            &config.theme,
+        );
+        let stream = fs::File::open(filename)?;
+        let data = Arc::new(FileData {
+            index,
+            title,
+            lines: Mutex::new(Vec::new()),
+            loaded: Mutex::new(false),
+            highlighter: Mutex::new(highlighter),
+            highlight_cursor: Mutex::new(0),
+        });
+        spawn_reader(data.clone(), stream, event_sender, config.tab_width);
+        Ok(File(data))
+    }
+
+    /// Spawn `command` and load its stdout and stderr as two separate
+    /// files.
+    pub(crate) fn new_command<I, S>(
+        index: usize,
+        command: &OsStr,
+        args: I,
+        title: &str,
+        event_sender: EventSender,
+        config: &Config,
+    ) -> Result<(Self, Self)>
+    where
+        I: IntoIterator<Item = S>,
+        S: AsRef<OsStr>,
+    {
+        let mut child = Command::new(command)
+            .args(args)
+            .stdout(Stdio::piped())
+            .stderr(Stdio::piped())
+            .spawn()?;
+        let stdout = child.stdout.take().expect("stdout was piped");
+        let stderr = child.stderr.take().expect("stderr was piped");
+        let out_file = Self::new_streamed(index, stdout, title, event_sender.clone(), config)?;
+        let err_file = Self::new_streamed(index + 1, stderr, title, event_sender, config)?;
+        // The child is detached and reaped once its pipes are closed; we
+        // only care about its output.
+        thread::spawn(move || {
+            let _ = child.wait();
+        });
+        Ok((out_file, err_file))
+    }
+
+    /// This file's index amongst all the files attached to the pager.
+    pub(crate) fn index(&self) -> usize {
+        self.0.index
+    }
+
+    /// This file's display title.
+    pub(crate) fn title(&self) -> &str {
+        &self.0.title
+    }
+
+    /// Whether the file has finished loading (reached EOF).
+    pub(crate) fn is_loaded(&self) -> bool {
+        *self.0.loaded.lock().unwrap()
+    }
+
+    /// How many lines have been loaded so far.
+    pub(crate) fn line_count(&self) -> usize {
+        self.0.lines.lock().unwrap().len()
+    }
+
+    /// A snapshot of the (already tab-expanded) text loaded so far, in
+    /// this file's own line order.
+    pub(crate) fn lines_snapshot(&self) -> Vec<String> {
+        self.0
+            .lines
+            .lock()
+            .unwrap()
+            .iter()
+            .map(|(_, line)| line.text().to_string())
+            .collect()
+    }
+
+    /// A snapshot of the lines loaded so far, each tagged with its
+    /// pager-wide arrival sequence number. Used to reconstruct the true
+    /// arrival order across multiple files (e.g. a subprocess's stdout
+    /// interleaved with its stderr).
+    pub(crate) fn lines_with_sequence(&self) -> Vec<(u64, String)> {
+        self.0
+            .lines
+            .lock()
+            .unwrap()
+            .iter()
+            .map(|(seq, line)| (*seq, line.text().to_string()))
+            .collect()
+    }
+
+    /// The highlighting spans for line `index`, highlighting any lines up
+    /// to and including it that haven't been highlighted yet. Returns an
+    /// empty `Vec` (render plain) if the line isn't loaded yet, no syntax
+    /// matched, or the terminal can't show enough colors.
+    pub(crate) fn highlighted_line(&self, index: usize, color_level: ColorLevel) -> Vec<HighlightedSpan> {
+        let mut lines = self.0.lines.lock().unwrap();
+        if index >= lines.len() {
+            return Vec::new();
+        }
+        let mut cursor = self.0.highlight_cursor.lock().unwrap();
+        if *cursor <= index {
+            let mut highlighter = self.0.highlighter.lock().unwrap();
+            for (_, line) in &mut lines[*cursor..=index] {
+                if let Some(spans) = highlighter.highlight_line(line.text(), color_level) {
+                    *line = line.clone().with_highlight(spans);
+                }
+            }
+            *cursor = index + 1;
+        }
+        lines[index].1.highlight().to_vec()
+    }
+}
+
+fn spawn_reader(
+    data: Arc<FileData>,
+    stream: impl Read + Send + 'static,
+    event_sender: EventSender,
+    tab_width: usize,
+) {
+    thread::spawn(move || {
+        let mut reader = BufReader::new(stream);
+        loop {
+            let mut text = String::new();
+            match reader.read_line(&mut text) {
+                Ok(0) => break,
+                Ok(_) => {
+                    if text.ends_with('\n') {
+                        text.pop();
+                        if text.ends_with('\r') {
+                            text.pop();
+                        }
+                    }
+                    // Tabs are expanded once here, at ingestion, so that
+                    // every downstream consumer (rendering, search,
+                    // horizontal scroll) works off the same column-stable
+                    // text instead of re-deriving it (and potentially
+                    // drifting out of sync) in more than one place.
+                    let seq = event_sender.next_seq();
+                    data.lines.lock().unwrap().push((seq, Line::new(&text, tab_width)));
+                    event_sender.send(Event::Loaded(data.index));
+                }
+                Err(_) => break,
+            }
+        }
+        *data.loaded.lock().unwrap() = true;
+        event_sender.send(Event::Eof(data.index));
+    });
+}
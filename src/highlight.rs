@@ -0,0 +1,198 @@
+//! Syntax highlighting for paged file and stream content.
+//!
+//! Highlighting is line-oriented and stateful: a [`FileHighlighter`]
+//! carries its `syntect` parser state from one line to the next so that
+//! multi-line constructs (block comments, strings, ...) are highlighted
+//! correctly even though lines arrive incrementally. Highlighting is only
+//! ever done up to the last line a caller has asked about, so a
+//! multi-gigabyte stream is never parsed further than it has actually
+//! been viewed.
+
+use std::path::Path;
+use std::sync::OnceLock;
+
+use syntect::highlighting::{
+    FontStyle, HighlightIterator, HighlightState, Highlighter, Style as SynStyle, Theme, ThemeSet,
+};
+use syntect::parsing::{ParseState, ScopeStack, SyntaxSet};
+use termwiz::caps::ColorLevel;
+use termwiz::cell::{CellAttributes, Intensity, Underline};
+use termwiz::color::{ColorAttribute, RgbColor};
+
+/// The default theme used when none is configured or the configured name
+/// isn't found.
+pub(crate) const DEFAULT_THEME: &str = "base16-ocean.dark";
+
+/// A run of text sharing a single style, as produced by highlighting a
+/// line.
+#[derive(Clone, Debug)]
+pub(crate) struct HighlightedSpan {
+    /// The span's text.
+    pub(crate) text: String,
+    /// The attributes to merge into the line's rendering for this span.
+    pub(crate) attrs: CellAttributes,
+}
+
+fn syntax_set() -> &'static SyntaxSet {
+    static SYNTAX_SET: OnceLock<SyntaxSet> = OnceLock::new();
+    SYNTAX_SET.get_or_init(SyntaxSet::load_defaults_newlines)
+}
+
+fn theme_set() -> &'static ThemeSet {
+    static THEME_SET: OnceLock<ThemeSet> = OnceLock::new();
+    THEME_SET.get_or_init(ThemeSet::load_defaults)
+}
+
+/// The names of the bundled highlighting themes.
+pub(crate) fn theme_names() -> impl Iterator<Item = &'static str> {
+    theme_set().themes.keys().map(String::as_str)
+}
+
+fn theme(name: &str) -> &'static Theme {
+    theme_set()
+        .themes
+        .get(name)
+        .unwrap_or_else(|| &theme_set().themes[DEFAULT_THEME])
+}
+
+/// Highlights a single file's lines, maintaining parser state across
+/// calls.
+pub(crate) struct FileHighlighter {
+    theme_name: String,
+    parse_state: Option<ParseState>,
+    highlight_state: Option<HighlightState>,
+}
+
+impl FileHighlighter {
+    /// Build a highlighter for a file, picking a syntax from (in order)
+    /// an explicit override, the filename's extension, or the stream's
+    /// title, falling back to no highlighting if nothing matches.
+    pub(crate) fn new(
+        filename: Option<&Path>,
+        title: &str,
+        syntax_override: Option<&str>,
+        theme_name: &str,
+    ) -> Self {
+        let syntax_set = syntax_set();
+        let syntax = syntax_override
+            .and_then(|name| syntax_set.find_syntax_by_name(name))
+            .or_else(|| filename.and_then(|path| syntax_set.find_syntax_for_file(path).ok().flatten()))
+            .or_else(|| syntax_set.find_syntax_by_extension(Path::new(title).extension()?.to_str()?))
+            .or_else(|| syntax_set.find_syntax_by_token(title));
+
+        let (parse_state, highlight_state) = match syntax {
+            Some(syntax) => {
+                let highlighter = Highlighter::new(theme(theme_name));
+                let parse_state = ParseState::new(syntax);
+                let highlight_state = HighlightState::new(&highlighter, ScopeStack::new());
+                (Some(parse_state), Some(highlight_state))
+            }
+            None => (None, None),
+        };
+
+        FileHighlighter {
+            theme_name: theme_name.to_string(),
+            parse_state,
+            highlight_state,
+        }
+    }
+
+    /// Highlight the next line of this file, advancing the carried parser
+    /// state. Returns `None` if no syntax matched, or if `color_level` is
+    /// below 256-color (i.e. monochrome or 16-color), in which case the
+    /// line should be rendered plain rather than with spans carrying
+    /// truecolor attributes the terminal can't show.
+    pub(crate) fn highlight_line(
+        &mut self,
+        line: &str,
+        color_level: ColorLevel,
+    ) -> Option<Vec<HighlightedSpan>> {
+        if matches!(color_level, ColorLevel::MonoChrome | ColorLevel::Sixteen) {
+            return None;
+        }
+        let parse_state = self.parse_state.as_mut()?;
+        let highlight_state = self.highlight_state.as_mut()?;
+
+        let syntax_set = syntax_set();
+        let highlighter = Highlighter::new(theme(&self.theme_name));
+
+        // syntect's line-oriented parser expects the trailing newline to
+        // be present so that end-of-line scope transitions are tracked.
+        let mut owned_line = line.to_string();
+        owned_line.push('\n');
+
+        let ops = parse_state.parse_line(&owned_line, syntax_set).ok()?;
+        let spans: Vec<HighlightedSpan> =
+            HighlightIterator::new(highlight_state, &ops, &owned_line, &highlighter)
+                .map(|(style, text)| HighlightedSpan {
+                    text: text.trim_end_matches('\n').to_string(),
+                    attrs: style_to_attrs(style),
+                })
+                .collect();
+        Some(spans)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn block_comment_state_carries_across_lines() {
+        let mut highlighter = FileHighlighter::new(None, "test.c", Some("C"), DEFAULT_THEME);
+
+        let opening = highlighter
+            .highlight_line("/* a comment", ColorLevel::TrueColor)
+            .expect("C syntax should match");
+        let continued = highlighter
+            .highlight_line("   still inside the comment */", ColorLevel::TrueColor)
+            .expect("C syntax should match");
+        let code = highlighter
+            .highlight_line("int x = 0;", ColorLevel::TrueColor)
+            .expect("C syntax should match");
+
+        // The comment didn't close on the first line, so the carried
+        // parser state means its leading text is still highlighted as a
+        // comment on the second call, not as top-level code.
+        assert_eq!(opening[0].attrs.foreground(), continued[0].attrs.foreground());
+        // Once the comment actually closes, later code is highlighted
+        // differently again.
+        assert_ne!(continued[0].attrs.foreground(), code[0].attrs.foreground());
+    }
+
+    #[test]
+    fn monochrome_falls_back_to_plain() {
+        let mut highlighter = FileHighlighter::new(None, "test.c", Some("C"), DEFAULT_THEME);
+        assert!(highlighter
+            .highlight_line("int x = 0;", ColorLevel::MonoChrome)
+            .is_none());
+    }
+
+    #[test]
+    fn sixteen_color_falls_back_to_plain() {
+        let mut highlighter = FileHighlighter::new(None, "test.c", Some("C"), DEFAULT_THEME);
+        assert!(highlighter
+            .highlight_line("int x = 0;", ColorLevel::Sixteen)
+            .is_none());
+    }
+}
+
+fn style_to_attrs(style: SynStyle) -> CellAttributes {
+    let mut attrs = CellAttributes::default();
+    attrs.set_foreground(ColorAttribute::TrueColorWithDefaultFallback(
+        RgbColor::new(style.foreground.r, style.foreground.g, style.foreground.b).into(),
+    ));
+    attrs.set_background(ColorAttribute::TrueColorWithDefaultFallback(
+        RgbColor::new(style.background.r, style.background.g, style.background.b).into(),
+    ));
+    if style.font_style.contains(FontStyle::BOLD) {
+        attrs.set_intensity(Intensity::Bold);
+    }
+    if style.font_style.contains(FontStyle::UNDERLINE) {
+        attrs.set_underline(Underline::Single);
+    }
+    if style.font_style.contains(FontStyle::ITALIC) {
+        attrs.set_italic(true);
+    }
+    attrs
+}
@@ -21,6 +21,7 @@ mod direct;
 mod display;
 mod event;
 mod file;
+mod highlight;
 mod line;
 mod line_cache;
 mod line_drawing;
@@ -32,6 +33,7 @@ mod refresh;
 mod ruler;
 mod screen;
 mod search;
+mod signal;
 mod util;
 
 use config::{Config, InterfaceMode};
@@ -61,6 +63,11 @@ pub struct Pager {
 
     /// Configuration.
     config: Config,
+
+    /// Disarms the process-wide Ctrl-C handler when this `Pager` is
+    /// dropped or finishes running, so it doesn't keep swallowing Ctrl-C
+    /// for the rest of the host process.
+    signal_guard: signal::SignalHandlerGuard,
 }
 
 /// Determine terminal capabilities.
@@ -80,6 +87,17 @@ fn termcaps() -> Result<Capabilities> {
     Ok(caps)
 }
 
+/// Finish building a stderr-UI `Pager`: degrade to a non-interactive
+/// passthrough if stderr, the output it will draw to, isn't a terminal.
+fn finish_stdio_and_stderr(mut pager: Pager) -> Pager {
+    use termwiz::istty::IsTty;
+
+    if !std::io::stderr().is_tty() {
+        pager.set_interface_mode(InterfaceMode::Direct);
+    }
+    pager
+}
+
 impl Pager {
     /// Build a `Pager` using the system terminal.
     pub fn new_using_system_terminal() -> Result<Self> {
@@ -109,14 +127,47 @@ impl Pager {
         Self::new_with_terminal_func(move |caps| SystemTerminal::new_with(caps, input, output))
     }
 
+    #[cfg(unix)]
+    /// Build a `Pager` that draws its interactive UI (screen, bar, ruler,
+    /// prompts) to stderr instead of stdout, reading input from stdin as
+    /// usual. This leaves stdout a clean passthrough for callers who want
+    /// to consume it downstream, the way `bat` reserves stdout for the
+    /// file it's paging rather than its own UI chrome.
+    ///
+    /// If stderr isn't a terminal, the returned `Pager` automatically
+    /// degrades to a non-interactive passthrough: `run()` copies every
+    /// attached file and stream straight to stdout, in order, instead of
+    /// trying to page.
+    pub fn new_using_stdio_and_stderr() -> Result<Self> {
+        Self::new_with_input_output(&std::io::stdin(), &std::io::stderr())
+            .map(finish_stdio_and_stderr)
+    }
+
+    #[cfg(windows)]
+    /// Build a `Pager` that draws its interactive UI (screen, bar, ruler,
+    /// prompts) to stderr instead of stdout, reading input from stdin as
+    /// usual. This leaves stdout a clean passthrough for callers who want
+    /// to consume it downstream, the way `bat` reserves stdout for the
+    /// file it's paging rather than its own UI chrome.
+    ///
+    /// If stderr isn't a terminal, the returned `Pager` automatically
+    /// degrades to a non-interactive passthrough: `run()` copies every
+    /// attached file and stream straight to stdout, in order, instead of
+    /// trying to page.
+    pub fn new_using_stdio_and_stderr() -> Result<Self> {
+        Self::new_with_input_output(std::io::stdin(), std::io::stderr())
+            .map(finish_stdio_and_stderr)
+    }
+
     fn new_with_terminal_func(
         create_term: impl FnOnce(Capabilities) -> Result<SystemTerminal>,
     ) -> Result<Self> {
         let caps = termcaps()?;
-        let mut term = create_term(caps.clone())?;
-        term.set_raw_mode()?;
+        let term = create_term(caps.clone())?;
 
         let events = EventStream::new(term.waker());
+        let signal_guard = signal::install_signal_handler(events.sender())?;
+
         let files = Vec::new();
         let error_files = VecMap::new();
         let progress = None;
@@ -130,6 +181,7 @@ impl Pager {
             error_files,
             progress,
             config,
+            signal_guard,
         })
     }
 
@@ -141,7 +193,7 @@ impl Pager {
     ) -> Result<&mut Self> {
         let index = self.files.len();
         let event_sender = self.events.sender();
-        let file = File::new_streamed(index, stream, title, event_sender)?;
+        let file = File::new_streamed(index, stream, title, event_sender, &self.config)?;
         self.files.push(file);
         Ok(self)
     }
@@ -154,7 +206,7 @@ impl Pager {
     ) -> Result<&mut Self> {
         let index = self.files.len();
         let event_sender = self.events.sender();
-        let file = File::new_streamed(index, stream, title, event_sender)?;
+        let file = File::new_streamed(index, stream, title, event_sender, &self.config)?;
         if let Some(out_file) = self.files.last() {
             self.error_files.insert(out_file.index(), file.clone());
         }
@@ -166,7 +218,7 @@ impl Pager {
     pub fn add_output_file(&mut self, filename: &OsStr) -> Result<&mut Self> {
         let index = self.files.len();
         let event_sender = self.events.sender();
-        let file = File::new_file(index, filename, event_sender)?;
+        let file = File::new_file(index, filename, event_sender, &self.config)?;
         self.files.push(file);
         Ok(self)
     }
@@ -184,20 +236,40 @@ impl Pager {
     {
         let index = self.files.len();
         let event_sender = self.events.sender();
-        let (out_file, err_file) = File::new_command(index, command, args, title, event_sender)?;
+        let (out_file, err_file) =
+            File::new_command(index, command, args, title, event_sender, &self.config)?;
         self.error_files.insert(index, err_file.clone());
         self.files.push(out_file);
         self.files.push(err_file);
         Ok(self)
     }
 
-    /// Set the progress stream.
+    /// Set the progress stream. It may report on any number of
+    /// concurrently-updating, named progress bars; see
+    /// [`Pager::set_progress_format`] for how they're rendered.
     pub fn set_progress_stream(&mut self, stream: impl Read + Send + 'static) -> &mut Self {
         let event_sender = self.events.sender();
         self.progress = Some(Progress::new(stream, event_sender));
         self
     }
 
+    /// Set the template used to render each progress bar.
+    ///
+    /// Supports the placeholders `{bar}` (a width-aware filled/empty
+    /// glyph run sized to whatever columns remain), `{pos}`, `{len}`,
+    /// `{percent}`, `{msg}`, `{elapsed}`, and `{id}`.
+    pub fn set_progress_format(&mut self, template: impl Into<String>) -> &mut Self {
+        self.config.progress_format = template.into();
+        self
+    }
+
+    /// Set how long a progress bar can go without an update before it's
+    /// pruned from the display.
+    pub fn set_progress_prune_interval(&mut self, interval: std::time::Duration) -> &mut Self {
+        self.config.progress_prune_after = interval;
+        self
+    }
+
     /// Set when to use full screen mode. See [`InterfaceMode`] for details.
     pub fn set_interface_mode(&mut self, value: impl Into<InterfaceMode>) -> &mut Self {
         self.config.interface_mode = value.into();
@@ -216,9 +288,56 @@ impl Pager {
         self
     }
 
+    /// Customize a themeable part of the interface with a ripgrep-style
+    /// `{item}:{attr}:{value}` spec, e.g. `match:fg:magenta` or
+    /// `ruler:bg:#202020`.
+    ///
+    /// `item` is one of `match`, `match-current`, `status`, `ruler`, or
+    /// `progress`; `attr` is `fg`, `bg`, or `style`; and
+    /// `value` is a named ANSI color, a `0..=255` palette index, an
+    /// `r,g,b`/`#rrggbb` truecolor triple, or (for `style`) one of `bold`,
+    /// `dim`, `underline`, `reverse`, `italic`, `none`. Returns an error if
+    /// the spec is malformed.
+    pub fn set_color(&mut self, spec: &str) -> Result<&mut Self> {
+        self.config.colors.apply_spec(spec)?;
+        Ok(self)
+    }
+
+    /// Force a specific syntax (by name) to be used for highlighting every
+    /// attached file, instead of auto-detecting one from each file's name
+    /// or stream title.
+    pub fn set_syntax(&mut self, name: impl Into<String>) -> &mut Self {
+        self.config.syntax = Some(name.into());
+        self
+    }
+
+    /// Select the syntax highlighting theme by name.
+    pub fn set_theme(&mut self, name: impl Into<String>) -> &mut Self {
+        self.config.theme = name.into();
+        self
+    }
+
+    /// Set how many columns a hard tab expands to. A width of `0` leaves
+    /// tabs unexpanded, passing them through to the terminal as-is.
+    pub fn set_tab_width(&mut self, width: usize) -> &mut Self {
+        self.config.tab_width = width;
+        self
+    }
+
+    /// Search for `query` in the paged content, highlighting matches using
+    /// the `match`/`match-current` colors.
+    pub fn set_search_query(&mut self, query: impl Into<String>) -> &mut Self {
+        self.config.search_query = Some(query.into());
+        self
+    }
+
     /// Run Stream Pager.
     pub fn run(self) -> Result<()> {
-        display::start(
+        // `signal_guard` is held across the call and only dropped (disarming
+        // the Ctrl-C handler) once `display::start` returns, so a quit
+        // during the run doesn't race a host program's own Ctrl-C handling.
+        let signal_guard = self.signal_guard;
+        let result = display::start(
             self.term,
             self.caps,
             self.events,
@@ -226,6 +345,8 @@ impl Pager {
             self.error_files,
             self.progress,
             self.config,
-        )
+        );
+        drop(signal_guard);
+        result
     }
 }
@@ -0,0 +1,55 @@
+//! A single logical line of paged content, ready for rendering.
+
+use crate::highlight::HighlightedSpan;
+use crate::line_drawing;
+
+/// A logical line of content, along with the rendering metadata needed to
+/// draw it (and count how many terminal rows it wraps to).
+#[derive(Clone, Debug)]
+pub(crate) struct Line {
+    text: String,
+    /// Syntax highlighting spans for this line, if any matched; empty
+    /// means render the line plain (and let overstrike/ANSI handling
+    /// apply its own styling).
+    highlight: Vec<HighlightedSpan>,
+}
+
+impl Line {
+    /// Wrap raw text (already split on newlines) into a `Line`, expanding
+    /// hard tabs to `tab_width` columns (`0` leaves tabs unexpanded). This
+    /// is done once, up front, so that search match offsets and
+    /// horizontal scroll math downstream all operate on the same,
+    /// already-expanded text.
+    pub(crate) fn new(text: &str, tab_width: usize) -> Self {
+        Line {
+            text: line_drawing::expand_tabs(text, tab_width),
+            highlight: Vec::new(),
+        }
+    }
+
+    /// Attach syntax highlighting spans to this line. The spans' combined
+    /// text must reconstruct the line's text; callers get that guarantee
+    /// from `highlight::FileHighlighter::highlight_line`.
+    pub(crate) fn with_highlight(mut self, highlight: Vec<HighlightedSpan>) -> Self {
+        self.highlight = highlight;
+        self
+    }
+
+    /// The line's text, with hard tabs already expanded.
+    pub(crate) fn text(&self) -> &str {
+        &self.text
+    }
+
+    /// This line's highlighting spans, merged over by overstrike/ANSI
+    /// handling for any attributes the terminal content itself requests.
+    /// Empty if the line has no highlighting (render plain).
+    pub(crate) fn highlight(&self) -> &[HighlightedSpan] {
+        &self.highlight
+    }
+
+    /// How many terminal rows this line occupies once wrapped to `width`
+    /// columns.
+    pub(crate) fn wrapped_row_count(&self, width: usize) -> usize {
+        line_drawing::wrapped_row_count(&self.text, width)
+    }
+}
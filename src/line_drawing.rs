@@ -0,0 +1,61 @@
+//! Helpers for measuring and laying out rendered lines.
+
+use unicode_width::UnicodeWidthChar;
+
+/// The display width, in terminal columns, of a single logical line.
+///
+/// Zero-width characters (e.g. combining marks) contribute no columns and
+/// wide (e.g. CJK) characters contribute two, matching how the terminal
+/// will actually render them.
+pub(crate) fn display_width(line: &str) -> usize {
+    line.chars()
+        .map(|ch| UnicodeWidthChar::width(ch).unwrap_or(0))
+        .sum()
+}
+
+/// Expand hard tabs in `line` to spaces, so that each tab advances to the
+/// next multiple of `tab_width` display columns.
+///
+/// Column accounting is display-width aware (wide/CJK characters count as
+/// two columns, zero-width characters as zero), so tab stops land on the
+/// same columns the terminal will actually draw them at. `tab_width == 0`
+/// passes `line` through unchanged, leaving any hard tabs in place.
+///
+/// Downstream column math (search match highlighting, horizontal scroll
+/// offsets) operates on the string this returns, so it stays consistent
+/// with what's rendered.
+pub(crate) fn expand_tabs(line: &str, tab_width: usize) -> String {
+    if tab_width == 0 || !line.contains('\t') {
+        return line.to_string();
+    }
+    let mut out = String::with_capacity(line.len());
+    let mut column = 0;
+    for ch in line.chars() {
+        if ch == '\t' {
+            let spaces = tab_width - (column % tab_width);
+            out.extend(std::iter::repeat(' ').take(spaces));
+            column += spaces;
+        } else {
+            out.push(ch);
+            column += UnicodeWidthChar::width(ch).unwrap_or(0);
+        }
+    }
+    out
+}
+
+/// How many terminal rows `line` will occupy once wrapped to `width`
+/// columns.
+///
+/// A line narrower than `width` (including empty lines) always takes up
+/// exactly one row. `width` of `0` is treated as unbounded (no wrapping).
+pub(crate) fn wrapped_row_count(line: &str, width: usize) -> usize {
+    if width == 0 {
+        return 1;
+    }
+    let cols = display_width(line);
+    if cols == 0 {
+        1
+    } else {
+        (cols + width - 1) / width
+    }
+}
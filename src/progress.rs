@@ -0,0 +1,203 @@
+//! Rendering one or more named progress bars, fed by an external stream.
+//!
+//! Each update line on the stream is of the form
+//! `id:field=value;field=value;...`, e.g. `build:pos=10;len=100;msg=Compiling`.
+//! Fields (`pos`, `len`, `msg`, `elapsed`) are all optional and only
+//! overwrite what's present; a bar that is only ever given a `msg` has no
+//! known fraction complete, so its `{bar}` renders as an all-empty glyph
+//! run.
+
+use std::collections::HashMap;
+use std::io::{BufRead, BufReader, Read};
+use std::sync::{Arc, Mutex};
+use std::thread;
+use std::time::{Duration, Instant};
+
+use termwiz::cell::CellAttributes;
+
+use crate::config::{ColorItem, Config};
+use crate::event::{Event, EventSender};
+use crate::line_drawing;
+
+/// A single named progress bar's last-known state.
+#[derive(Clone, Debug, Default)]
+struct Bar {
+    position: Option<u64>,
+    length: Option<u64>,
+    message: String,
+    elapsed: Option<Duration>,
+}
+
+struct LiveBar {
+    bar: Bar,
+    last_update: Instant,
+}
+
+/// A set of concurrently-updating, named progress bars.
+pub(crate) struct Progress {
+    bars: Arc<Mutex<HashMap<String, LiveBar>>>,
+}
+
+impl Progress {
+    /// Start tracking progress reported on `stream`, one update per line.
+    pub(crate) fn new(stream: impl Read + Send + 'static, event_sender: EventSender) -> Self {
+        let bars: Arc<Mutex<HashMap<String, LiveBar>>> = Arc::new(Mutex::new(HashMap::new()));
+        let thread_bars = bars.clone();
+        thread::spawn(move || {
+            let mut reader = BufReader::new(stream);
+            let mut line = String::new();
+            loop {
+                line.clear();
+                match reader.read_line(&mut line) {
+                    Ok(0) | Err(_) => break,
+                    Ok(_) => {
+                        if let Some((id, update)) = parse_update(line.trim_end()) {
+                            let mut bars = thread_bars.lock().unwrap();
+                            let live = bars.entry(id).or_insert_with(|| LiveBar {
+                                bar: Bar::default(),
+                                last_update: Instant::now(),
+                            });
+                            update.apply(&mut live.bar);
+                            live.last_update = Instant::now();
+                            event_sender.send(Event::Progress);
+                        }
+                    }
+                }
+            }
+        });
+        Progress { bars }
+    }
+
+    /// Remove bars that haven't been updated within `prune_after`.
+    fn prune(&self, prune_after: Duration) {
+        self.bars
+            .lock()
+            .unwrap()
+            .retain(|_, live| live.last_update.elapsed() < prune_after);
+    }
+
+    /// Render every live bar, stacked, using `template` and sized to
+    /// `width` terminal columns. Bars are ordered by id for a stable
+    /// layout. Stale bars (per `config.progress_prune_after`) are dropped
+    /// first.
+    pub(crate) fn render(&self, config: &Config, width: usize) -> Vec<String> {
+        self.prune(config.progress_prune_after);
+        let bars = self.bars.lock().unwrap();
+        let mut ids: Vec<&String> = bars.keys().collect();
+        ids.sort();
+        ids.into_iter()
+            .map(|id| render_one(id, &bars[id].bar, &config.progress_format, width))
+            .collect()
+    }
+}
+
+/// A parsed update for one bar, with only the fields that were present in
+/// the update line set.
+#[derive(Default)]
+struct Update {
+    position: Option<u64>,
+    length: Option<u64>,
+    message: Option<String>,
+    elapsed: Option<Duration>,
+}
+
+impl Update {
+    fn apply(self, bar: &mut Bar) {
+        if let Some(position) = self.position {
+            bar.position = Some(position);
+        }
+        if let Some(length) = self.length {
+            bar.length = Some(length);
+        }
+        if let Some(message) = self.message {
+            bar.message = message;
+        }
+        if let Some(elapsed) = self.elapsed {
+            bar.elapsed = Some(elapsed);
+        }
+    }
+}
+
+fn parse_update(line: &str) -> Option<(String, Update)> {
+    let (id, fields) = line.split_once(':')?;
+    if id.is_empty() {
+        return None;
+    }
+    let mut update = Update::default();
+    for field in fields.split(';').filter(|f| !f.is_empty()) {
+        let (key, value) = field.split_once('=')?;
+        match key {
+            "pos" => update.position = value.parse().ok(),
+            "len" => update.length = value.parse().ok(),
+            "msg" => update.message = Some(value.to_string()),
+            "elapsed" => update.elapsed = value.parse().ok().map(Duration::from_secs),
+            _ => {}
+        }
+    }
+    Some((id.to_string(), update))
+}
+
+fn percent(bar: &Bar) -> String {
+    match (bar.position, bar.length) {
+        (Some(pos), Some(len)) if len > 0 => format!("{}%", (pos * 100 / len).min(100)),
+        _ => String::new(),
+    }
+}
+
+fn elapsed(bar: &Bar) -> String {
+    match bar.elapsed {
+        Some(elapsed) => {
+            let secs = elapsed.as_secs();
+            if secs >= 60 {
+                format!("{}m{:02}s", secs / 60, secs % 60)
+            } else {
+                format!("{}s", secs)
+            }
+        }
+        None => String::new(),
+    }
+}
+
+/// Expand every placeholder except `{bar}`.
+fn substitute_fields(template: &str, bar: &Bar) -> String {
+    template
+        .replace("{pos}", &bar.position.map(|p| p.to_string()).unwrap_or_default())
+        .replace("{len}", &bar.length.map(|l| l.to_string()).unwrap_or_default())
+        .replace("{percent}", &percent(bar))
+        .replace("{msg}", &bar.message)
+        .replace("{elapsed}", &elapsed(bar))
+}
+
+/// A width-aware filled/empty glyph run representing the bar's fraction
+/// complete, `width` columns wide.
+fn glyph_bar(bar: &Bar, width: usize) -> String {
+    if width == 0 {
+        return String::new();
+    }
+    let fraction = match (bar.position, bar.length) {
+        (Some(pos), Some(len)) if len > 0 => (pos as f64 / len as f64).clamp(0.0, 1.0),
+        _ => 0.0,
+    };
+    let filled = ((width as f64) * fraction).round() as usize;
+    let filled = filled.min(width);
+    let mut glyphs = String::with_capacity(width);
+    glyphs.extend(std::iter::repeat('█').take(filled));
+    glyphs.extend(std::iter::repeat('░').take(width - filled));
+    glyphs
+}
+
+fn render_one(id: &str, bar: &Bar, template: &str, width: usize) -> String {
+    if !template.contains("{bar}") {
+        return substitute_fields(&template.replace("{id}", id), bar);
+    }
+    let without_bar = substitute_fields(&template.replace("{bar}", "").replace("{id}", id), bar);
+    let reserved = line_drawing::display_width(&without_bar);
+    let bar_width = width.saturating_sub(reserved);
+    let glyph = glyph_bar(bar, bar_width);
+    substitute_fields(&template.replace("{bar}", &glyph).replace("{id}", id), bar)
+}
+
+/// The attributes progress output should be drawn with.
+pub(crate) fn attributes(config: &Config) -> CellAttributes {
+    config.colors.get(ColorItem::Progress)
+}
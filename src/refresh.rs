@@ -0,0 +1,31 @@
+//! Recomputing the screen's reserved-row layout — the progress, ruler,
+//! and status regions — against the terminal's current size, so a
+//! resize (or a progress bar being added or pruned) is reflected the
+//! next time the screen redraws.
+
+/// Rows reserved below the content viewport for the fixed ruler line and
+/// status bar, not counting however many progress bars are currently
+/// live.
+const FIXED_ROWS: usize = 2;
+
+/// How many of the terminal's `rows` are available for content, given
+/// `progress_rows` currently-rendered progress lines.
+pub(crate) fn content_rows(rows: usize, progress_rows: usize) -> usize {
+    rows.saturating_sub(progress_rows + FIXED_ROWS)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn reserves_progress_rows_alongside_the_ruler_and_status_bar() {
+        assert_eq!(content_rows(24, 0), 22);
+        assert_eq!(content_rows(24, 3), 19);
+    }
+
+    #[test]
+    fn a_resize_leaving_no_room_for_content_saturates_at_zero() {
+        assert_eq!(content_rows(2, 5), 0);
+    }
+}
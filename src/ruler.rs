@@ -0,0 +1,10 @@
+//! The ruler showing the cursor's current position in the file.
+
+use termwiz::cell::CellAttributes;
+
+use crate::config::{ColorItem, Config};
+
+/// The attributes the ruler should be drawn with.
+pub(crate) fn attributes(config: &Config) -> CellAttributes {
+    config.colors.get(ColorItem::Ruler)
+}
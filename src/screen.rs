@@ -0,0 +1,156 @@
+//! Drawing a full frame: the content viewport, the ruler, and the status
+//! bar.
+
+use anyhow::Result;
+use termwiz::caps::Capabilities;
+use termwiz::cell::CellAttributes;
+use termwiz::surface::change::Change;
+use termwiz::terminal::{SystemTerminal, Terminal};
+
+use crate::bar;
+use crate::config::Config;
+use crate::file::File;
+use crate::highlight::HighlightedSpan;
+use crate::progress::{self, Progress};
+use crate::refresh;
+use crate::ruler;
+use crate::search;
+
+/// Redraw the whole screen: as many content lines as fit above the
+/// reserved progress/ruler/status rows, then those reserved rows
+/// themselves.
+pub(crate) fn render(
+    term: &mut SystemTerminal,
+    caps: &Capabilities,
+    files: &[File],
+    progress: Option<&Progress>,
+    config: &Config,
+    top_line: usize,
+) -> Result<()> {
+    let size = term.get_screen_size()?;
+    let (rows, cols) = (size.rows, size.cols);
+    let color_level = caps.color_level();
+
+    let progress_lines = progress
+        .map(|progress| progress.render(config, cols))
+        .unwrap_or_default();
+    let content_rows = refresh::content_rows(rows, progress_lines.len());
+
+    let mut changes = vec![Change::ClearScreen(Default::default())];
+
+    if let Some(file) = files.first() {
+        let lines = file.lines_snapshot();
+        for (row, line) in lines.iter().enumerate().skip(top_line).take(content_rows) {
+            let spans = file.highlighted_line(row, color_level);
+            render_line(&mut changes, line, &spans, config);
+            changes.push(Change::Text("\r\n".to_string()));
+        }
+    }
+
+    for line in &progress_lines {
+        changes.push(Change::AllAttributes(progress::attributes(config)));
+        changes.push(Change::Text(format!("{}\r\n", line)));
+    }
+
+    changes.push(Change::AllAttributes(ruler::attributes(config)));
+    changes.push(Change::Text(format!("-- line {} --\r\n", top_line + 1)));
+
+    changes.push(Change::AllAttributes(bar::attributes(config)));
+    changes.push(Change::Text(status_text(files)));
+
+    term.render(&changes)?;
+    term.flush()?;
+    Ok(())
+}
+
+/// Render one content line: its syntax highlighting spans if any matched,
+/// overlaid with any active search matches, otherwise its plain text with
+/// those same matches picked out.
+fn render_line(changes: &mut Vec<Change>, text: &str, spans: &[HighlightedSpan], config: &Config) {
+    let query = config.search_query.as_deref().unwrap_or("");
+    let matches = if query.is_empty() {
+        Vec::new()
+    } else {
+        search::find_matches(text, query)
+    };
+
+    if spans.is_empty() {
+        render_plain(changes, text, &matches, config);
+    } else {
+        render_highlighted(changes, text, spans, &matches, config);
+    }
+}
+
+fn render_plain(changes: &mut Vec<Change>, text: &str, matches: &[search::Match], config: &Config) {
+    if matches.is_empty() {
+        changes.push(Change::Text(text.to_string()));
+        return;
+    }
+
+    let mut cursor = 0;
+    for (index, m) in matches.iter().enumerate() {
+        changes.push(Change::Text(text[cursor..m.start].to_string()));
+        changes.push(Change::AllAttributes(search::attributes(config, index == 0)));
+        changes.push(Change::Text(text[m.start..m.end].to_string()));
+        changes.push(Change::AllAttributes(Default::default()));
+        cursor = m.end;
+    }
+    changes.push(Change::Text(text[cursor..].to_string()));
+}
+
+/// Render a syntax-highlighted line, overlaying the search-match
+/// attributes over whichever spans an active search match falls in,
+/// rather than letting the syntax colors silently suppress it.
+fn render_highlighted(
+    changes: &mut Vec<Change>,
+    text: &str,
+    spans: &[HighlightedSpan],
+    matches: &[search::Match],
+    config: &Config,
+) {
+    let mut offset = 0;
+    for span in spans {
+        let span_end = offset + span.text.len();
+        render_span(changes, text, offset, span_end, &span.attrs, matches, config);
+        offset = span_end;
+    }
+    changes.push(Change::AllAttributes(Default::default()));
+}
+
+/// Render one highlighted span's `[span_start, span_end)` byte range,
+/// splitting out whichever part of it overlaps an active search match so
+/// that sub-range can be drawn with the search attributes instead of
+/// `base_attrs`.
+fn render_span(
+    changes: &mut Vec<Change>,
+    text: &str,
+    span_start: usize,
+    span_end: usize,
+    base_attrs: &CellAttributes,
+    matches: &[search::Match],
+    config: &Config,
+) {
+    let mut cursor = span_start;
+    for (index, m) in matches.iter().enumerate() {
+        let overlap_start = m.start.max(span_start);
+        let overlap_end = m.end.min(span_end);
+        if overlap_start >= overlap_end {
+            continue;
+        }
+        if overlap_start > cursor {
+            changes.push(Change::AllAttributes(base_attrs.clone()));
+            changes.push(Change::Text(text[cursor..overlap_start].to_string()));
+        }
+        changes.push(Change::AllAttributes(search::attributes(config, index == 0)));
+        changes.push(Change::Text(text[overlap_start..overlap_end].to_string()));
+        cursor = overlap_end;
+    }
+    if cursor < span_end {
+        changes.push(Change::AllAttributes(base_attrs.clone()));
+        changes.push(Change::Text(text[cursor..span_end].to_string()));
+    }
+}
+
+fn status_text(files: &[File]) -> String {
+    files.first().map(File::title).unwrap_or("").to_string()
+}
@@ -0,0 +1,59 @@
+//! Searching loaded content for matches.
+
+use termwiz::cell::CellAttributes;
+
+use crate::config::{ColorItem, Config};
+
+/// A match found while searching, as a byte range within its line.
+///
+/// Callers must search `Line::text()` (already tab-expanded), not the raw
+/// line read from the file, so these offsets stay consistent with both
+/// the rendered columns and horizontal scroll math, which operate on the
+/// same expanded text.
+#[derive(Clone, Copy, Debug)]
+pub(crate) struct Match {
+    pub(crate) start: usize,
+    pub(crate) end: usize,
+}
+
+/// Find every occurrence of `needle` in `line`.
+pub(crate) fn find_matches(line: &str, needle: &str) -> Vec<Match> {
+    if needle.is_empty() {
+        return Vec::new();
+    }
+    line.match_indices(needle)
+        .map(|(start, matched)| Match {
+            start,
+            end: start + matched.len(),
+        })
+        .collect()
+}
+
+/// The attributes a search match should be drawn with.
+pub(crate) fn attributes(config: &Config, is_current: bool) -> CellAttributes {
+    let item = if is_current {
+        ColorItem::MatchCurrent
+    } else {
+        ColorItem::Match
+    };
+    config.colors.get(item)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::line::Line;
+
+    #[test]
+    fn matches_land_on_tab_expanded_columns() {
+        // A hard tab at column 1 expands to column 4 with a tab width of
+        // 4, so "bc" actually starts at (display, and here byte) column
+        // 4, not column 2 as it would in the raw, unexpanded line.
+        let line = Line::new("a\tbc", 4);
+        let matches = find_matches(line.text(), "bc");
+        assert_eq!(line.text(), "a   bc");
+        assert_eq!(matches.len(), 1);
+        assert_eq!(matches[0].start, 4);
+        assert_eq!(matches[0].end, 6);
+    }
+}
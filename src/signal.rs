@@ -0,0 +1,137 @@
+//! Guaranteed terminal restoration and signal handling.
+//!
+//! A terminal left in raw mode, in the alternate screen, or with a
+//! hidden cursor because of an interrupt or a panic mid-render is
+//! unpleasant for the user, since most shells don't reset it for them.
+//! [`TerminalGuard`] restores all three on drop, including while the
+//! stack is unwinding from a panic, and [`install_signal_handler`] turns
+//! SIGINT/SIGWINCH-style signals into ordinary [`Event`]s so `run()`
+//! unwinds through its normal cleanup path instead of the process simply
+//! dying mid-frame.
+
+use std::io::Write;
+use std::sync::{Mutex, Once, OnceLock};
+
+use anyhow::Result;
+use termwiz::surface::change::Change;
+use termwiz::surface::CursorVisibility;
+use termwiz::terminal::{SystemTerminal, Terminal};
+
+use crate::event::{Event, EventSender};
+
+/// RAII guard that restores the terminal to a usable state on drop:
+/// cursor shown, alternate screen left, raw mode disabled. Unconditional
+/// and best-effort, so it still runs (and can't itself panic) while
+/// unwinding from a panic elsewhere in the render loop.
+pub(crate) struct TerminalGuard<'a> {
+    term: &'a mut SystemTerminal,
+}
+
+impl<'a> TerminalGuard<'a> {
+    /// Put the terminal into raw mode, returning a guard that will
+    /// restore it (and leave the alternate screen, and show the cursor)
+    /// once dropped.
+    pub(crate) fn enter(term: &'a mut SystemTerminal) -> Result<Self> {
+        term.set_raw_mode()?;
+        Ok(TerminalGuard { term })
+    }
+}
+
+impl<'a> Drop for TerminalGuard<'a> {
+    fn drop(&mut self) {
+        // Best-effort and infallible: we're potentially already unwinding
+        // from a panic, so there's nothing better to do with an error
+        // here than ignore it.
+        let _ = self
+            .term
+            .render(&[Change::CursorVisibility(CursorVisibility::Visible)]);
+        let _ = write!(self.term, "\x1b[?1049l");
+        let _ = self.term.flush();
+        let _ = self.term.set_cooked_mode();
+    }
+}
+
+/// The event sender for whichever `Pager` currently owns the process-wide
+/// `ctrlc` handler, or `None` if no `Pager` is active. `ctrlc::set_handler`
+/// can only be called once per process, so the handler itself is installed
+/// at most once (guarded by `INSTALL`) and forwards to whichever sender is
+/// currently registered here, falling back to the OS's default SIGINT
+/// behavior when no `Pager` is active so a host program's own Ctrl-C
+/// handling isn't silently swallowed after `run()` returns.
+fn active_sender() -> &'static Mutex<Option<EventSender>> {
+    static ACTIVE: OnceLock<Mutex<Option<EventSender>>> = OnceLock::new();
+    ACTIVE.get_or_init(|| Mutex::new(None))
+}
+
+/// Disarms the `ctrlc` handler (restoring default SIGINT behavior) when
+/// dropped, so a `Pager` only swallows Ctrl-C from its construction until
+/// it is dropped or its `run()` call returns, and, on unix, stops and joins the SIGWINCH forwarding
+/// thread `install_signal_handler` spawned for this `Pager` so neither it
+/// nor its `signal_hook` registration outlives `run()`.
+pub(crate) struct SignalHandlerGuard {
+    #[cfg(unix)]
+    resize_signals: Option<(signal_hook::iterator::Handle, std::thread::JoinHandle<()>)>,
+}
+
+impl Drop for SignalHandlerGuard {
+    fn drop(&mut self) {
+        *active_sender().lock().unwrap() = None;
+
+        #[cfg(unix)]
+        if let Some((handle, thread)) = self.resize_signals.take() {
+            handle.close();
+            let _ = thread.join();
+        }
+    }
+}
+
+/// Install a handler that turns process-level interrupt and resize
+/// signals into [`Event`]s on `event_sender`, so the display loop can
+/// react to them the same way it reacts to any other event, rather than
+/// the default OS behavior of aborting the process.
+///
+/// Returns a guard that disarms the handler (falling back to the OS
+/// default again) when dropped; hold it for as long as `event_sender`
+/// should keep receiving `Quit` events.
+pub(crate) fn install_signal_handler(event_sender: EventSender) -> Result<SignalHandlerGuard> {
+    static INSTALL: Once = Once::new();
+    let mut install_err = None;
+    INSTALL.call_once(|| {
+        let result = ctrlc::set_handler(|| match active_sender().lock().unwrap().clone() {
+            Some(sender) => sender.send(Event::Quit),
+            None => {
+                let _ = signal_hook::low_level::emulate_default_handler(signal_hook::consts::SIGINT);
+            }
+        });
+        if let Err(error) = result {
+            install_err = Some(error);
+        }
+    });
+    if let Some(error) = install_err {
+        return Err(error.into());
+    }
+
+    *active_sender().lock().unwrap() = Some(event_sender.clone());
+
+    #[cfg(unix)]
+    let resize_signals = {
+        // SIGWINCH has no portable handling via `ctrlc`; use `signal-hook`
+        // to forward it onto the same event channel as everything else.
+        // The `Handle` is kept so `SignalHandlerGuard::drop` can close the
+        // registration and join this thread instead of leaking both for
+        // the rest of the process.
+        let mut signals = signal_hook::iterator::Signals::new([signal_hook::consts::SIGWINCH])?;
+        let handle = signals.handle();
+        let thread = std::thread::spawn(move || {
+            for _ in signals.forever() {
+                event_sender.send(Event::Resize);
+            }
+        });
+        Some((handle, thread))
+    };
+
+    Ok(SignalHandlerGuard {
+        #[cfg(unix)]
+        resize_signals,
+    })
+}